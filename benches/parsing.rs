@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use good_git::object::Object;
+use good_git::parse;
+
+fn bench_header(c: &mut Criterion) {
+    let data = b"blob 16\0what is up, doc?";
+
+    c.bench_function("header/manual", |b| {
+        b.iter(|| Object::parse_header(black_box(data)))
+    });
+    c.bench_function("header/nom", |b| b.iter(|| parse::header(black_box(data))));
+}
+
+fn bench_tree_entries(c: &mut Criterion) {
+    let content = b"100644 file1.txt\0\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x11\x12\x13\x14\
+        100644 file2.txt\0\x51\x52\x53\x54\x55\x56\x57\x58\x59\x5a\x5b\x5c\x5d\x5e\x5f\x60\x61\x62\x63\x64\
+        40000 folder\0\x81\x82\x83\x84\x85\x86\x87\x88\x89\x8a\x8b\x8c\x8d\x8e\x8f\x90\x91\x92\x93\x94";
+
+    c.bench_function("tree_entries/manual", |b| {
+        b.iter(|| Object::parse_tree_manual(black_box(content), 20))
+    });
+    c.bench_function("tree_entries/nom", |b| {
+        b.iter(|| parse::tree_entries(black_box(content), 20))
+    });
+}
+
+criterion_group!(benches, bench_header, bench_tree_entries);
+criterion_main!(benches);
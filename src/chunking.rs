@@ -0,0 +1,380 @@
+//! A content-defined chunking store for large blobs, alongside the loose
+//! object store in [`crate::object`]. Splitting a blob into chunks that
+//! reshuffle themselves around edits (rather than monolithic zlib blobs)
+//! lets unrelated files, and successive versions of the same file, share
+//! storage for any chunk whose content didn't change.
+
+use anyhow::{anyhow, Context, Result};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::prelude::*;
+use std::io::SeekFrom;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::object::{hash, ObjectFormat};
+
+/// Boundaries for the rolling-hash chunker. A boundary is declared once
+/// `min_size` bytes have accumulated since the last one and the Gear hash's
+/// low bits (sized for an expected run length of `avg_size`) are all zero,
+/// or unconditionally once `max_size` bytes have accumulated. Keeping a
+/// content-defined (rather than fixed-size) boundary means an insertion or
+/// deletion only perturbs the chunks touching it, not every chunk after it.
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkerConfig {
+    pub min_size: usize,
+    pub avg_size: usize,
+    pub max_size: usize,
+}
+
+impl ChunkerConfig {
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> ChunkerConfig {
+        ChunkerConfig {
+            min_size,
+            avg_size,
+            max_size,
+        }
+    }
+
+    fn mask(&self) -> u64 {
+        (self.avg_size.next_power_of_two() as u64) - 1
+    }
+}
+
+impl Default for ChunkerConfig {
+    fn default() -> Self {
+        ChunkerConfig::new(2 * 1024, 8 * 1024, 64 * 1024)
+    }
+}
+
+/// Splits `content` into content-defined chunks, returning each chunk's
+/// byte range within `content`.
+pub fn chunk_ranges(content: &[u8], config: &ChunkerConfig) -> Vec<Range<usize>> {
+    if content.is_empty() {
+        return vec![];
+    }
+
+    let mask = config.mask();
+    let mut ranges = vec![];
+    let mut start = 0;
+    let mut rolling_hash: u64 = 0;
+
+    for (i, &byte) in content.iter().enumerate() {
+        rolling_hash = rolling_hash.wrapping_shl(1).wrapping_add(gear_table()[byte as usize]);
+        let len = i - start + 1;
+
+        if (len >= config.min_size && rolling_hash & mask == 0) || len >= config.max_size {
+            ranges.push(start..i + 1);
+            start = i + 1;
+            rolling_hash = 0;
+        }
+    }
+
+    if start < content.len() {
+        ranges.push(start..content.len());
+    }
+
+    ranges
+}
+
+/// A deterministic 256-entry Gear table, lazily filled by a splitmix64
+/// stream so we don't have to hand-write 256 "random" constants.
+fn gear_table() -> &'static [u64; 256] {
+    static TABLE: OnceLock<[u64; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0u64; 256];
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        for entry in table.iter_mut() {
+            seed = splitmix64(seed);
+            *entry = seed;
+        }
+        table
+    })
+}
+
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// One chunk's location: an id (the chunk's content hash) plus an offset
+/// and length. In a [`Manifest`] the offset is the chunk's position within
+/// the reassembled blob; in the on-disk chunk index it is the chunk's
+/// position within the packed `chunks` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkRef {
+    pub id: String,
+    pub offset: u64,
+    pub length: u64,
+}
+
+/// The ordered list of chunks that reassembles into a blob's content.
+#[derive(Debug, PartialEq)]
+pub struct Manifest {
+    pub chunks: Vec<ChunkRef>,
+}
+
+impl Manifest {
+    /// Serializes to one `"<chunk-id> <offset> <length>\n"` line per chunk.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        for chunk in &self.chunks {
+            buf.extend(format!("{} {} {}\n", chunk.id, chunk.offset, chunk.length).into_bytes());
+        }
+        buf
+    }
+
+    pub fn parse(content: &[u8]) -> Result<Manifest> {
+        let text = std::str::from_utf8(content).context("Manifest is not valid UTF-8")?;
+        let mut chunks = vec![];
+        for line in text.lines() {
+            let mut parts = line.split_whitespace();
+            let id = parts
+                .next()
+                .ok_or_else(|| anyhow!("Missing chunk id"))?
+                .to_string();
+            let offset = parts
+                .next()
+                .ok_or_else(|| anyhow!("Missing chunk offset"))?
+                .parse::<u64>()
+                .context("Invalid chunk offset")?;
+            let length = parts
+                .next()
+                .ok_or_else(|| anyhow!("Missing chunk length"))?
+                .parse::<u64>()
+                .context("Invalid chunk length")?;
+            chunks.push(ChunkRef { id, offset, length });
+        }
+        Ok(Manifest { chunks })
+    }
+}
+
+/// Chunks `content`, appends any chunk not already present to
+/// `root/chunks`, and stores a [`Manifest`] object (addressed the same way
+/// loose objects are) listing every chunk in order. Returns the manifest's
+/// object id.
+pub fn write_blob(
+    root: &Path,
+    content: &[u8],
+    format: ObjectFormat,
+    config: &ChunkerConfig,
+) -> Result<String> {
+    std::fs::create_dir_all(root).context("Could not create chunk store root")?;
+    let mut index = load_chunk_index(root)?;
+
+    let chunks_path = root.join("chunks");
+    let mut chunks_file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&chunks_path)
+        .context("Could not open chunks file")?;
+    let mut append_offset = chunks_file
+        .metadata()
+        .context("Could not stat chunks file")?
+        .len();
+
+    let mut manifest_chunks = vec![];
+    let mut blob_offset: u64 = 0;
+    for range in chunk_ranges(content, config) {
+        let bytes = &content[range];
+        let id = hash(bytes, format);
+        let length = bytes.len() as u64;
+
+        if !index.contains_key(&id) {
+            chunks_file
+                .write_all(bytes)
+                .context("Could not append chunk")?;
+            let entry = ChunkRef {
+                id: id.clone(),
+                offset: append_offset,
+                length,
+            };
+            append_chunk_index(root, &entry)?;
+            index.insert(id.clone(), entry);
+            append_offset += length;
+        }
+
+        manifest_chunks.push(ChunkRef {
+            id,
+            offset: blob_offset,
+            length,
+        });
+        blob_offset += length;
+    }
+
+    store_manifest(root, &Manifest { chunks: manifest_chunks }, format)
+}
+
+/// Reassembles a blob previously written with [`write_blob`] by looking up
+/// and concatenating the chunks listed in its manifest.
+pub fn read_blob(root: &Path, manifest_id: &str) -> Result<Vec<u8>> {
+    let manifest = load_manifest(root, manifest_id)?;
+    let index = load_chunk_index(root)?;
+
+    let chunks_path = root.join("chunks");
+    let mut chunks_file = std::fs::File::open(&chunks_path).context("Could not open chunks file")?;
+
+    let mut out = Vec::new();
+    for chunk in &manifest.chunks {
+        let entry = index
+            .get(&chunk.id)
+            .ok_or_else(|| anyhow!("Chunk {} missing from chunk store", chunk.id))?;
+
+        chunks_file
+            .seek(SeekFrom::Start(entry.offset))
+            .context("Could not seek in chunks file")?;
+        let mut buf = vec![0u8; entry.length as usize];
+        chunks_file
+            .read_exact(&mut buf)
+            .context("Could not read chunk")?;
+        out.extend(buf);
+    }
+
+    Ok(out)
+}
+
+fn load_chunk_index(root: &Path) -> Result<HashMap<String, ChunkRef>> {
+    let index_path = root.join("chunks.idx");
+    if !index_path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content = std::fs::read(&index_path).context("Could not read chunk index")?;
+    let manifest = Manifest::parse(&content)?;
+    Ok(manifest
+        .chunks
+        .into_iter()
+        .map(|entry| (entry.id.clone(), entry))
+        .collect())
+}
+
+fn append_chunk_index(root: &Path, entry: &ChunkRef) -> Result<()> {
+    let index_path = root.join("chunks.idx");
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&index_path)
+        .context("Could not open chunk index")?;
+    file.write_all(format!("{} {} {}\n", entry.id, entry.offset, entry.length).as_bytes())
+        .context("Could not append to chunk index")
+}
+
+fn store_manifest(root: &Path, manifest: &Manifest, format: ObjectFormat) -> Result<String> {
+    let payload = manifest.serialize();
+    let header = format!("chunk-manifest {}\0", payload.len());
+    let mut data = header.into_bytes();
+    data.extend(payload);
+
+    let id = hash(&data, format);
+    let (dir, file) = id.split_at(2);
+    let object_dir = root.join("objects").join(dir);
+    std::fs::create_dir_all(&object_dir).context("Could not create manifest directory")?;
+
+    let object_path = object_dir.join(file);
+    if object_path.exists() {
+        return Ok(id);
+    }
+
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    encoder
+        .write_all(&data)
+        .context("Could not compress manifest")?;
+    let compressed = encoder.finish().context("Could not finish compression")?;
+    std::fs::write(&object_path, compressed).context("Could not write manifest")?;
+
+    Ok(id)
+}
+
+fn load_manifest(root: &Path, manifest_id: &str) -> Result<Manifest> {
+    let (dir, file) = manifest_id.split_at(2);
+    let object_path = root.join("objects").join(dir).join(file);
+    let data = std::fs::read(&object_path).context("Could not read manifest")?;
+
+    let mut z = ZlibDecoder::new(&data[..]);
+    let mut s = vec![];
+    z.read_to_end(&mut s).context("Could not decompress manifest")?;
+
+    let (content, (object_type, object_size)) =
+        crate::parse::header(&s).map_err(|e| crate::parse::describe_error(&s, e))?;
+    if object_type != "chunk-manifest" {
+        return Err(anyhow!(
+            "Expected a chunk-manifest object, found {object_type}"
+        ));
+    }
+    if content.len() != object_size {
+        return Err(anyhow!("Incorrect manifest header length"));
+    }
+
+    Manifest::parse(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_content() -> Vec<u8> {
+        // Large enough, and varied enough, to cross chunk boundaries under
+        // the default ChunkerConfig.
+        (0..200_000u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_chunk_ranges_are_deterministic() {
+        let content = sample_content();
+        let config = ChunkerConfig::default();
+
+        let first = chunk_ranges(&content, &config);
+        let second = chunk_ranges(&content, &config);
+        assert_eq!(first, second);
+        assert!(first.len() > 1);
+
+        // The ranges must tile the whole input with no gaps or overlaps.
+        let mut expected_start = 0;
+        for range in &first {
+            assert_eq!(range.start, expected_start);
+            expected_start = range.end;
+        }
+        assert_eq!(expected_start, content.len());
+    }
+
+    #[test]
+    fn test_write_blob_and_read_blob_round_trip() {
+        let dir = std::env::temp_dir().join(format!("good_git_chunking_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let content = sample_content();
+        let config = ChunkerConfig::default();
+        let manifest_id =
+            write_blob(&dir, &content, ObjectFormat::Sha1, &config).unwrap();
+        let read_back = read_blob(&dir, &manifest_id).unwrap();
+
+        assert_eq!(read_back, content);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_blob_dedups_identical_chunks() {
+        let dir = std::env::temp_dir().join(format!("good_git_chunking_dedup_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let content = sample_content();
+        let config = ChunkerConfig::default();
+
+        write_blob(&dir, &content, ObjectFormat::Sha1, &config).unwrap();
+        let size_after_first = std::fs::metadata(dir.join("chunks")).unwrap().len();
+
+        write_blob(&dir, &content, ObjectFormat::Sha1, &config).unwrap();
+        let size_after_second = std::fs::metadata(dir.join("chunks")).unwrap().len();
+
+        assert_eq!(size_after_first, size_after_second);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
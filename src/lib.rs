@@ -0,0 +1,4 @@
+pub mod chunking;
+pub mod object;
+pub mod pack;
+pub mod parse;
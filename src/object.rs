@@ -1,7 +1,30 @@
 use anyhow::{anyhow, Context, Result};
 use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
 use sha1::{Digest, Sha1};
+use sha2::Sha256;
 use std::io::prelude::*;
+use std::path::Path;
+
+/// The hash algorithm a repository's objects are addressed by: the classic
+/// 20-byte SHA-1 format, or git's newer 32-byte SHA-256 object format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObjectFormat {
+    #[default]
+    Sha1,
+    Sha256,
+}
+
+impl ObjectFormat {
+    /// The size in bytes of a raw (non-hex) object id under this format.
+    pub fn hash_len(self) -> usize {
+        match self {
+            ObjectFormat::Sha1 => 20,
+            ObjectFormat::Sha256 => 32,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Blob {
@@ -13,13 +36,13 @@ impl Blob {
         Blob { content }
     }
 
-    pub fn hash(self) -> String {
+    pub fn hash(self, format: ObjectFormat) -> String {
         let size = self.content.len();
         let data = format!("blob {size}\0");
         let mut data = data.as_bytes().to_vec();
         data.extend(self.content);
 
-        hash(&data)
+        hash(&data, format)
     }
 }
 
@@ -32,6 +55,36 @@ impl Tree {
     pub fn new(files: Vec<File>) -> Tree {
         Tree { files }
     }
+
+    /// Serializes the tree back into the canonical
+    /// `"<mode> <name>\0<raw hash bytes>"` form (20 bytes for a SHA-1 id, 32
+    /// for a SHA-256 id), with entries sorted the way git sorts them
+    /// (directory names compare as if suffixed with `/`).
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        let mut files: Vec<&File> = self.files.iter().collect();
+        files.sort_by_key(|a| tree_sort_key(a));
+
+        let mut buf = vec![];
+        for file in files {
+            buf.extend_from_slice(file.mode.as_bytes());
+            buf.push(b' ');
+            buf.extend_from_slice(file.name.as_bytes());
+            buf.push(0);
+            buf.extend_from_slice(&hex::decode(&file.hash).context("Invalid entry hash")?);
+        }
+        Ok(buf)
+    }
+}
+
+/// Git sorts tree entries by name, treating directories as if their name
+/// had a trailing `/`, so e.g. a directory `foo` sorts after a file
+/// `foo.txt` (`/` is greater than `.`), while a file `foo` sorts before it.
+fn tree_sort_key(file: &File) -> Vec<u8> {
+    let mut key = file.name.as_bytes().to_vec();
+    if file.type_str() == "tree" {
+        key.push(b'/');
+    }
+    key
 }
 
 #[derive(Debug, PartialEq)]
@@ -60,16 +113,161 @@ impl File {
     }
 }
 
+/// A `name <email>` signature plus the unix timestamp and timezone offset
+/// that follow it on `author`/`committer`/`tagger` lines.
+#[derive(Debug, PartialEq)]
+pub struct Signature {
+    pub name: String,
+    pub email: String,
+    pub timestamp: i64,
+    pub timezone: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Commit {
+    pub tree: String,
+    pub parents: Vec<String>,
+    pub author: Signature,
+    pub committer: Signature,
+    pub message: String,
+}
+
+impl Commit {
+    /// Parses a commit body: a `tree` line, zero or more `parent` lines, an
+    /// `author` line, a `committer` line, a blank line, then the message.
+    pub fn parse(content: &[u8]) -> Result<Commit> {
+        let text = std::str::from_utf8(content).context("Commit is not valid UTF-8")?;
+        let header_end = text
+            .find("\n\n")
+            .ok_or_else(|| anyhow!("Missing commit message separator"))?;
+        let (header, message) = text.split_at(header_end);
+        let message = message.trim_start_matches('\n').to_string();
+
+        let mut lines = header.lines();
+        let tree = lines
+            .next()
+            .and_then(|l| l.strip_prefix("tree "))
+            .ok_or_else(|| anyhow!("Missing tree line"))?
+            .to_string();
+
+        let mut parents = vec![];
+        let mut author = None;
+        let mut committer = None;
+        for line in lines {
+            if let Some(parent) = line.strip_prefix("parent ") {
+                parents.push(parent.to_string());
+            } else if let Some(rest) = line.strip_prefix("author ") {
+                author = Some(parse_signature(rest)?);
+            } else if let Some(rest) = line.strip_prefix("committer ") {
+                committer = Some(parse_signature(rest)?);
+            }
+            // Any other header line (e.g. `gpgsig ...` and its
+            // space-indented continuation lines on a signed commit) is
+            // skipped rather than rejected — we don't model signatures,
+            // but commits carrying one are too common to hard-error on.
+        }
+
+        Ok(Commit {
+            tree,
+            parents,
+            author: author.ok_or_else(|| anyhow!("Missing author line"))?,
+            committer: committer.ok_or_else(|| anyhow!("Missing committer line"))?,
+            message,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Tag {
+    pub object: String,
+    pub kind: String,
+    pub name: String,
+    pub tagger: Signature,
+    pub message: String,
+}
+
+impl Tag {
+    /// Parses an annotated tag body: `object`, `type`, `tag` and `tagger`
+    /// lines, a blank line, then the message.
+    pub fn parse(content: &[u8]) -> Result<Tag> {
+        let text = std::str::from_utf8(content).context("Tag is not valid UTF-8")?;
+        let header_end = text
+            .find("\n\n")
+            .ok_or_else(|| anyhow!("Missing tag message separator"))?;
+        let (header, message) = text.split_at(header_end);
+        let message = message.trim_start_matches('\n').to_string();
+
+        let mut lines = header.lines();
+        let object = lines
+            .next()
+            .and_then(|l| l.strip_prefix("object "))
+            .ok_or_else(|| anyhow!("Missing object line"))?
+            .to_string();
+        let kind = lines
+            .next()
+            .and_then(|l| l.strip_prefix("type "))
+            .ok_or_else(|| anyhow!("Missing type line"))?
+            .to_string();
+        let name = lines
+            .next()
+            .and_then(|l| l.strip_prefix("tag "))
+            .ok_or_else(|| anyhow!("Missing tag line"))?
+            .to_string();
+        let tagger = lines
+            .next()
+            .and_then(|l| l.strip_prefix("tagger "))
+            .ok_or_else(|| anyhow!("Missing tagger line"))?;
+        let tagger = parse_signature(tagger)?;
+
+        Ok(Tag {
+            object,
+            kind,
+            name,
+            tagger,
+            message,
+        })
+    }
+}
+
+/// Parses the `Name <email> <unix-ts> <tz>` signature that follows the
+/// `author `/`committer `/`tagger ` prefix on a header line.
+fn parse_signature(rest: &str) -> Result<Signature> {
+    let lt = rest.find('<').ok_or_else(|| anyhow!("Invalid signature"))?;
+    let gt = rest.find('>').ok_or_else(|| anyhow!("Invalid signature"))?;
+    let name = rest[..lt].trim().to_string();
+    let email = rest[lt + 1..gt].to_string();
+
+    let mut parts = rest[gt + 1..].split_whitespace();
+    let timestamp = parts
+        .next()
+        .ok_or_else(|| anyhow!("Missing timestamp"))?
+        .parse::<i64>()
+        .context("Invalid timestamp")?;
+    let timezone = parts
+        .next()
+        .ok_or_else(|| anyhow!("Missing timezone"))?
+        .to_string();
+
+    Ok(Signature {
+        name,
+        email,
+        timestamp,
+        timezone,
+    })
+}
+
 #[derive(Debug)]
 pub enum Object {
     Blob(Blob),
     Tree(Tree),
+    Commit(Commit),
+    Tag(Tag),
 }
 
 impl Object {
-    pub fn from_bytes(s: &[u8]) -> Result<Object> {
-        let (object_type, object_size, header_end) = Object::parse_header(s)?;
-        let mut content = &s[header_end + 1..];
+    pub fn from_bytes(s: &[u8], format: ObjectFormat) -> Result<Object> {
+        let (content, (object_type, object_size)) =
+            crate::parse::header(s).map_err(|e| crate::parse::describe_error(s, e))?;
 
         if content.len() != object_size {
             return Err(anyhow!("Incorrect header length"));
@@ -81,48 +279,62 @@ impl Object {
                 Ok(Object::Blob(blob))
             }
             "tree" => {
-                // Format (one per file/folder/tree/submodule):
-                // [mode] [object name]\0[SHA-1 in binary format (20 bytes)]
-                let mut files = vec![];
-                while !content.is_empty() {
-                    let mut mode = vec![];
-                    let mode_size = content
-                        .read_until(b' ', &mut mode)
-                        .context("Failed to read mode")?;
-                    let mode = std::str::from_utf8(&mode[..mode_size - 1])?;
-
-                    let mut name = vec![];
-                    let name_size = content
-                        .read_until(b'\0', &mut name)
-                        .context("Failed to read file name")?;
-                    let name = std::str::from_utf8(&name[..name_size - 1])?;
-
-                    let mut hash = [0_u8; 20];
-                    content
-                        .read_exact(&mut hash)
-                        .context("Failed to read hash")?;
-                    let hash = hex::encode(hash);
-
-                    files.push(File {
-                        mode: mode.to_string(),
-                        name: name.to_string(),
-                        hash,
-                    });
-                }
-                let tree = Tree::new(files);
-                Ok(Object::Tree(tree))
+                let (_, files) = crate::parse::tree_entries(content, format.hash_len())
+                    .map_err(|e| crate::parse::describe_error(content, e))?;
+                Ok(Object::Tree(Tree::new(files)))
             }
+            "commit" => Ok(Object::Commit(Commit::parse(content)?)),
+            "tag" => Ok(Object::Tag(Tag::parse(content)?)),
             _ => Err(anyhow!("Unknown object type")),
         }
     }
 
-    pub fn from_file(path: &std::path::Path) -> Result<Object> {
+    /// Hand-rolled tree-entry parsing kept as a benchmark baseline against
+    /// [`crate::parse::tree_entries`] — same format, same output, no `nom`.
+    pub fn parse_tree_manual(content: &[u8], hash_len: usize) -> Result<Vec<File>> {
+        let mut content = content;
+        let mut files = vec![];
+        while !content.is_empty() {
+            let mut mode = vec![];
+            let mode_size = content
+                .read_until(b' ', &mut mode)
+                .context("Failed to read mode")?;
+            let mode = std::str::from_utf8(&mode[..mode_size - 1])?;
+
+            let mut name = vec![];
+            let name_size = content
+                .read_until(b'\0', &mut name)
+                .context("Failed to read file name")?;
+            let name = std::str::from_utf8(&name[..name_size - 1])?;
+
+            let mut hash = vec![0_u8; hash_len];
+            content
+                .read_exact(&mut hash)
+                .context("Failed to read hash")?;
+            let hash = hex::encode(hash);
+
+            files.push(File {
+                mode: mode.to_string(),
+                name: name.to_string(),
+                hash,
+            });
+        }
+        Ok(files)
+    }
+
+    pub fn from_file(path: &std::path::Path, format: ObjectFormat) -> Result<Object> {
         let data = std::fs::read(path).context("Could not read from file")?;
         let mut z = ZlibDecoder::new(&data[..]);
         let mut s: Vec<u8> = vec![];
         z.read_to_end(&mut s)?;
 
-        Object::from_bytes(&s)
+        Object::from_bytes(&s, format)
+    }
+
+    /// Reads every object contained in a `.git/objects/pack/*.pack` file,
+    /// resolving `ofs-delta` and `ref-delta` entries along the way.
+    pub fn from_pack(path: &std::path::Path, format: ObjectFormat) -> Result<Vec<Object>> {
+        Ok(crate::pack::PackFile::open(path, format)?.objects)
     }
 
     /// Parse the header of a git object.
@@ -130,7 +342,10 @@ impl Object {
     /// The header is in the format: [object type] [object size]\0
     ///
     /// Returns the type, object size and the index where the header ends.
-    fn parse_header(s: &[u8]) -> Result<(String, usize, usize)> {
+    ///
+    /// Hand-rolled index math, kept as a benchmark baseline against
+    /// [`crate::parse::header`] rather than used by [`Object::from_bytes`].
+    pub fn parse_header(s: &[u8]) -> Result<(String, usize, usize)> {
         let space_index = s
             .iter()
             .position(|&x| x == b' ')
@@ -146,11 +361,58 @@ impl Object {
     }
 }
 
-pub fn hash(s: &[u8]) -> String {
-    let mut hasher = Sha1::new();
-    hasher.update(s);
+pub fn hash(s: &[u8], format: ObjectFormat) -> String {
+    match format {
+        ObjectFormat::Sha1 => {
+            let mut hasher = Sha1::new();
+            hasher.update(s);
+            hex::encode(hasher.finalize())
+        }
+        ObjectFormat::Sha256 => {
+            let mut hasher = Sha256::new();
+            hasher.update(s);
+            hex::encode(hasher.finalize())
+        }
+    }
+}
+
+/// Writes `obj` as a loose object under `root/objects/`, mirroring git's
+/// write path: build the `"<type> <len>\0"` header plus payload, hash it to
+/// get the object id, zlib-compress the buffer and store it fanned out by
+/// the first two hex characters of the id. Returns the object id.
+///
+/// If the object already exists on disk, the write is skipped.
+pub fn write_object(root: &Path, obj: &Object, format: ObjectFormat) -> Result<String> {
+    let (type_str, payload) = match obj {
+        Object::Blob(blob) => ("blob", blob.content.clone()),
+        Object::Tree(tree) => ("tree", tree.serialize()?),
+        Object::Commit(_) | Object::Tag(_) => {
+            return Err(anyhow!("Writing commit/tag objects is not supported yet"))
+        }
+    };
+
+    let header = format!("{type_str} {}\0", payload.len());
+    let mut data = header.into_bytes();
+    data.extend(payload);
+
+    let id = hash(&data, format);
+    let (dir, file) = id.split_at(2);
+    let object_dir = root.join("objects").join(dir);
+    std::fs::create_dir_all(&object_dir).context("Could not create object directory")?;
+
+    let object_path = object_dir.join(file);
+    if object_path.exists() {
+        return Ok(id);
+    }
+
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    encoder
+        .write_all(&data)
+        .context("Could not compress object")?;
+    let compressed = encoder.finish().context("Could not finish compression")?;
+    std::fs::write(&object_path, compressed).context("Could not write object")?;
 
-    hex::encode(hasher.finalize())
+    Ok(id)
 }
 
 #[cfg(test)]
@@ -158,8 +420,15 @@ mod tests {
     use crate::object::File;
 
     use super::hash;
+    use super::parse_signature;
+    use super::write_object;
     use super::Blob;
+    use super::Commit;
     use super::Object;
+    use super::ObjectFormat;
+    use super::Signature;
+    use super::Tag;
+    use super::Tree;
     #[test]
     fn test_object_parse_header() {
         assert_eq!(
@@ -183,7 +452,7 @@ mod tests {
     #[test]
     fn test_object_from_bytes_for_blob() {
         let s = b"blob 16\0what is up, doc?";
-        let object = Object::from_bytes(s.as_ref()).unwrap();
+        let object = Object::from_bytes(s.as_ref(), ObjectFormat::Sha1).unwrap();
         let Object::Blob(blob) = object else {
             panic!("Expected a Blob");
         };
@@ -196,7 +465,7 @@ mod tests {
             100644 file1.txt\0\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x11\x12\x13\x14\
             100644 file2.txt\0\x51\x52\x53\x54\x55\x56\x57\x58\x59\x5a\x5b\x5c\x5d\x5e\x5f\x60\x61\x62\x63\x64\
             40000 folder\0\x81\x82\x83\x84\x85\x86\x87\x88\x89\x8a\x8b\x8c\x8d\x8e\x8f\x90\x91\x92\x93\x94";
-        let object = Object::from_bytes(s.as_ref()).unwrap();
+        let object = Object::from_bytes(s.as_ref(), ObjectFormat::Sha1).unwrap();
         let Object::Tree(tree) = object else {
             panic!("Expected a tree");
         };
@@ -226,14 +495,21 @@ mod tests {
     fn test_object_from_bytes_for_tree_incorrect_hash_length() {
         let s = b"tree 18\0\
             100644 file1.txt\0\x01";
-        let err = Object::from_bytes(s.as_ref()).unwrap_err().to_string();
-        assert_eq!(err, "Failed to read hash");
+        let err = Object::from_bytes(s.as_ref(), ObjectFormat::Sha1)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "Parse error at byte offset 0: expected Eof, found 18 bytes remaining"
+        );
     }
 
     #[test]
     fn test_object_from_bytes_incorrect_header_size() {
         let s = b"blob 0\0hi";
-        let err = Object::from_bytes(s.as_ref()).unwrap_err().to_string();
+        let err = Object::from_bytes(s.as_ref(), ObjectFormat::Sha1)
+            .unwrap_err()
+            .to_string();
         assert_eq!(err, "Incorrect header length");
     }
 
@@ -241,13 +517,203 @@ mod tests {
     fn test_blob_hash_is_correct() {
         // From https://git-scm.com/book/sv/v2/Git-Internals-Git-Objects
         let blob = Blob::new(b"what is up, doc?".to_vec());
-        assert_eq!(blob.hash(), "bd9dbf5aae1a3862dd1526723246b20206e5fc37");
+        assert_eq!(
+            blob.hash(ObjectFormat::Sha1),
+            "bd9dbf5aae1a3862dd1526723246b20206e5fc37"
+        );
     }
 
     #[test]
     fn test_hash_is_correct() {
         // From https://git-scm.com/book/sv/v2/Git-Internals-Git-Objects
         let s = b"blob 16\0what is up, doc?";
-        assert_eq!(hash(s), "bd9dbf5aae1a3862dd1526723246b20206e5fc37");
+        assert_eq!(
+            hash(s, ObjectFormat::Sha1),
+            "bd9dbf5aae1a3862dd1526723246b20206e5fc37"
+        );
+    }
+
+    #[test]
+    fn test_hash_is_correct_for_sha256() {
+        let s = b"blob 16\0what is up, doc?";
+        assert_eq!(
+            hash(s, ObjectFormat::Sha256),
+            "7561bda2ad0a17be8fee9d1815a0896b80ebafddaf26cf30c228e9b320513033"
+        );
+    }
+
+    #[test]
+    fn test_object_from_bytes_for_tree_with_sha256_hashes() {
+        let s = b"tree 49\0\
+            100644 file1.txt\0\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\
+            \x11\x12\x13\x14\x15\x16\x17\x18\x19\x1a\x1b\x1c\x1d\x1e\x1f\x20";
+        let object = Object::from_bytes(s.as_ref(), ObjectFormat::Sha256).unwrap();
+        let Object::Tree(tree) = object else {
+            panic!("Expected a tree");
+        };
+        assert_eq!(tree.files.len(), 1);
+        assert_eq!(tree.files[0].name, "file1.txt");
+        assert_eq!(
+            tree.files[0].hash,
+            "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20"
+        );
+    }
+
+    #[test]
+    fn test_tree_serialize_sorts_entries_like_git() {
+        let tree = Tree::new(vec![
+            File {
+                mode: "100644".to_string(),
+                name: "foo.txt".to_string(),
+                hash: "0".repeat(40),
+            },
+            File {
+                mode: "40000".to_string(),
+                name: "foo".to_string(),
+                hash: "1".repeat(40),
+            },
+            File {
+                mode: "100644".to_string(),
+                name: "bar".to_string(),
+                hash: "2".repeat(40),
+            },
+        ]);
+
+        let serialized = tree.serialize().unwrap();
+        let (_, files) = crate::parse::tree_entries(&serialized, 20).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        // The directory "foo" sorts as "foo/", which is greater than
+        // "foo.txt" since '/' (0x2f) is greater than '.' (0x2e) — so the
+        // directory entry comes after the file entry of the same prefix.
+        assert_eq!(names, vec!["bar", "foo.txt", "foo"]);
+    }
+
+    #[test]
+    fn test_write_object_round_trip() {
+        let dir = std::env::temp_dir().join(format!("good_git_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let blob = Object::Blob(Blob::new(b"what is up, doc?".to_vec()));
+        let id = write_object(&dir, &blob, ObjectFormat::Sha1).unwrap();
+        assert_eq!(id, "bd9dbf5aae1a3862dd1526723246b20206e5fc37");
+
+        let path = dir.join("objects").join(&id[..2]).join(&id[2..]);
+        let read_back = Object::from_file(&path, ObjectFormat::Sha1).unwrap();
+        let Object::Blob(read_back) = read_back else {
+            panic!("Expected a Blob");
+        };
+        assert_eq!(read_back.content, b"what is up, doc?");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_signature() {
+        let sig = parse_signature("Bugs Bunny <bugs@acme.com> 1234567890 -0700").unwrap();
+        assert_eq!(
+            sig,
+            Signature {
+                name: "Bugs Bunny".to_string(),
+                email: "bugs@acme.com".to_string(),
+                timestamp: 1234567890,
+                timezone: "-0700".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_signature_malformed_email_brackets() {
+        assert_eq!(
+            parse_signature("Bugs Bunny bugs@acme.com 1234567890 -0700")
+                .unwrap_err()
+                .to_string(),
+            "Invalid signature"
+        );
+        assert_eq!(
+            parse_signature("Bugs Bunny <bugs@acme.com 1234567890 -0700")
+                .unwrap_err()
+                .to_string(),
+            "Invalid signature"
+        );
+    }
+
+    #[test]
+    fn test_commit_parse_with_multiple_parents() {
+        let content = b"tree deadbeef\n\
+            parent 1111111111111111111111111111111111111111\n\
+            parent 2222222222222222222222222222222222222222\n\
+            author Bugs Bunny <bugs@acme.com> 1234567890 -0700\n\
+            committer Daffy Duck <daffy@acme.com> 1234567891 -0700\n\
+            \n\
+            Merge branch 'b'\n";
+        let commit = Commit::parse(content).unwrap();
+        assert_eq!(
+            commit.parents,
+            vec![
+                "1111111111111111111111111111111111111111".to_string(),
+                "2222222222222222222222222222222222222222".to_string(),
+            ]
+        );
+        assert_eq!(commit.author.name, "Bugs Bunny");
+        assert_eq!(commit.committer.name, "Daffy Duck");
+        assert_eq!(commit.message, "Merge branch 'b'\n");
+    }
+
+    #[test]
+    fn test_commit_parse_missing_author() {
+        let content = b"tree deadbeef\n\
+            committer Daffy Duck <daffy@acme.com> 1234567891 -0700\n\
+            \n\
+            Initial commit\n";
+        let err = Commit::parse(content).unwrap_err().to_string();
+        assert_eq!(err, "Missing author line");
+    }
+
+    #[test]
+    fn test_commit_parse_missing_committer() {
+        let content = b"tree deadbeef\n\
+            author Bugs Bunny <bugs@acme.com> 1234567890 -0700\n\
+            \n\
+            Initial commit\n";
+        let err = Commit::parse(content).unwrap_err().to_string();
+        assert_eq!(err, "Missing committer line");
+    }
+
+    #[test]
+    fn test_commit_parse_skips_gpgsig() {
+        // `gpgsig`'s continuation lines each start with a single literal
+        // space, the same way git wraps them.
+        let content = b"tree deadbeef\nparent 1111111111111111111111111111111111111111\nauthor Bugs Bunny <bugs@acme.com> 1234567890 -0700\ncommitter Daffy Duck <daffy@acme.com> 1234567891 -0700\ngpgsig -----BEGIN PGP SIGNATURE-----\n iQEzBAABCAAdFiEE\n -----END PGP SIGNATURE-----\n\nSigned commit\n";
+        let commit = Commit::parse(content).unwrap();
+        assert_eq!(commit.author.name, "Bugs Bunny");
+        assert_eq!(commit.committer.name, "Daffy Duck");
+        assert_eq!(commit.message, "Signed commit\n");
+    }
+
+    #[test]
+    fn test_tag_parse() {
+        let content = b"object deadbeef\n\
+            type commit\n\
+            tag v1.0.0\n\
+            tagger Bugs Bunny <bugs@acme.com> 1234567890 -0700\n\
+            \n\
+            Release v1.0.0\n";
+        let tag = Tag::parse(content).unwrap();
+        assert_eq!(tag.object, "deadbeef");
+        assert_eq!(tag.kind, "commit");
+        assert_eq!(tag.name, "v1.0.0");
+        assert_eq!(tag.tagger.name, "Bugs Bunny");
+        assert_eq!(tag.message, "Release v1.0.0\n");
+    }
+
+    #[test]
+    fn test_tag_parse_missing_tagger() {
+        let content = b"object deadbeef\n\
+            type commit\n\
+            tag v1.0.0\n\
+            \n\
+            Release v1.0.0\n";
+        let err = Tag::parse(content).unwrap_err().to_string();
+        assert_eq!(err, "Missing tagger line");
     }
 }
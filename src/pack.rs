@@ -0,0 +1,663 @@
+use anyhow::{anyhow, Context, Result};
+use flate2::read::ZlibDecoder;
+use std::collections::{HashMap, HashSet};
+use std::io::prelude::*;
+use std::path::Path;
+
+use crate::object::{hash, Object, ObjectFormat};
+
+const MAGIC: &[u8; 4] = b"PACK";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryType {
+    Commit,
+    Tree,
+    Blob,
+    Tag,
+    OfsDelta,
+    RefDelta,
+}
+
+impl EntryType {
+    fn from_bits(bits: u8) -> Result<EntryType> {
+        match bits {
+            1 => Ok(EntryType::Commit),
+            2 => Ok(EntryType::Tree),
+            3 => Ok(EntryType::Blob),
+            4 => Ok(EntryType::Tag),
+            6 => Ok(EntryType::OfsDelta),
+            7 => Ok(EntryType::RefDelta),
+            _ => Err(anyhow!("Unknown pack entry type {bits}")),
+        }
+    }
+
+    fn type_str(self) -> &'static str {
+        match self {
+            EntryType::Commit => "commit",
+            EntryType::Tree => "tree",
+            EntryType::Blob => "blob",
+            EntryType::Tag => "tag",
+            EntryType::OfsDelta | EntryType::RefDelta => {
+                unreachable!("delta entries are resolved before being typed")
+            }
+        }
+    }
+}
+
+/// A raw entry as it appears in the pack, before delta resolution.
+enum RawEntry {
+    Base(EntryType, Vec<u8>),
+    OfsDelta(Vec<u8>, usize),
+    RefDelta(Vec<u8>, String),
+}
+
+/// A reader for the git PACK file format.
+///
+/// Enumerates every object stored in a `.pack` file, resolving `ofs-delta`
+/// and `ref-delta` entries against the other objects in the same pack.
+#[derive(Debug)]
+pub struct PackFile {
+    pub version: u32,
+    pub objects: Vec<Object>,
+}
+
+impl PackFile {
+    pub fn open(path: &Path, format: ObjectFormat) -> Result<PackFile> {
+        let data = std::fs::read(path).context("Could not read pack file")?;
+        PackFile::from_bytes(&data, format)
+    }
+
+    pub fn from_bytes(data: &[u8], format: ObjectFormat) -> Result<PackFile> {
+        let hash_len = format.hash_len();
+        if data.len() < 12 + hash_len || &data[0..4] != MAGIC {
+            return Err(anyhow!("Not a pack file"));
+        }
+        let version = u32::from_be_bytes(data[4..8].try_into().unwrap());
+        let count = u32::from_be_bytes(data[8..12].try_into().unwrap()) as usize;
+
+        let mut raw_entries = Vec::with_capacity(count);
+        let mut offset_to_index = HashMap::with_capacity(count);
+        let mut offset = 12;
+        for index in 0..count {
+            let entry_start = offset;
+            offset_to_index.insert(entry_start, index);
+
+            let (entry_type, size, header_len) = parse_entry_header(&data[offset..])?;
+            offset += header_len;
+
+            match entry_type {
+                EntryType::OfsDelta => {
+                    let (back_offset, varint_len) = parse_negative_offset(&data[offset..])?;
+                    offset += varint_len;
+                    let (delta, consumed) = inflate_at(&data[offset..], size)?;
+                    offset += consumed;
+                    let base_offset = entry_start
+                        .checked_sub(back_offset)
+                        .ok_or_else(|| anyhow!("ofs-delta base offset out of range"))?;
+                    raw_entries.push(RawEntry::OfsDelta(delta, base_offset));
+                }
+                EntryType::RefDelta => {
+                    let base_name = data
+                        .get(offset..offset + hash_len)
+                        .ok_or_else(|| anyhow!("Truncated ref-delta base name"))?;
+                    let base_name = hex::encode(base_name);
+                    offset += hash_len;
+                    let (delta, consumed) = inflate_at(&data[offset..], size)?;
+                    offset += consumed;
+                    raw_entries.push(RawEntry::RefDelta(delta, base_name));
+                }
+                _ => {
+                    let (content, consumed) = inflate_at(&data[offset..], size)?;
+                    offset += consumed;
+                    raw_entries.push(RawEntry::Base(entry_type, content));
+                }
+            }
+        }
+
+        if offset + hash_len != data.len() {
+            return Err(anyhow!("Trailing data after the last pack entry"));
+        }
+        let trailer = hex::encode(&data[offset..]);
+        let checksum = hash(&data[..offset], format);
+        if trailer != checksum {
+            return Err(anyhow!("Pack checksum mismatch"));
+        }
+
+        let mut resolved: Vec<Option<(EntryType, Vec<u8>)>> = (0..count).map(|_| None).collect();
+        let mut by_hash: HashMap<String, usize> = HashMap::new();
+        let mut in_progress = HashSet::new();
+        for index in 0..count {
+            resolve_entry(
+                index,
+                &raw_entries,
+                &offset_to_index,
+                &mut resolved,
+                &mut by_hash,
+                &mut in_progress,
+                format,
+            )?;
+        }
+
+        let objects = resolved
+            .into_iter()
+            .map(|entry| {
+                let (entry_type, content) = entry.ok_or_else(|| anyhow!("Unresolved pack entry"))?;
+                let header = format!("{} {}\0", entry_type.type_str(), content.len());
+                let mut buf = header.into_bytes();
+                buf.extend(content);
+                Object::from_bytes(&buf, format)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(PackFile { version, objects })
+    }
+}
+
+/// Resolves entry `index` into its final `(type, content)`, recursively
+/// resolving its delta base first if needed. Memoizes into `resolved` so
+/// that long delta chains are only walked once. `in_progress` tracks
+/// entries currently being resolved higher up the call stack, so a
+/// self-referential or cyclic delta chain errors instead of recursing
+/// forever.
+#[allow(clippy::too_many_arguments)]
+fn resolve_entry(
+    index: usize,
+    raw_entries: &[RawEntry],
+    offset_to_index: &HashMap<usize, usize>,
+    resolved: &mut Vec<Option<(EntryType, Vec<u8>)>>,
+    by_hash: &mut HashMap<String, usize>,
+    in_progress: &mut HashSet<usize>,
+    format: ObjectFormat,
+) -> Result<()> {
+    if resolved[index].is_some() {
+        return Ok(());
+    }
+    if !in_progress.insert(index) {
+        return Err(anyhow!(
+            "Cyclic or self-referential delta chain at pack entry {index}"
+        ));
+    }
+
+    let result = match &raw_entries[index] {
+        RawEntry::Base(entry_type, content) => (*entry_type, content.clone()),
+        RawEntry::OfsDelta(delta, base_offset) => {
+            let base_index = *offset_to_index
+                .get(base_offset)
+                .ok_or_else(|| anyhow!("ofs-delta does not point to an entry boundary"))?;
+            resolve_entry(
+                base_index,
+                raw_entries,
+                offset_to_index,
+                resolved,
+                by_hash,
+                in_progress,
+                format,
+            )?;
+            let (base_type, base_content) = resolved[base_index].clone().unwrap();
+            (base_type, apply_delta(&base_content, delta)?)
+        }
+        RawEntry::RefDelta(delta, base_name) => {
+            let base_index = resolve_ref_delta_base(
+                index,
+                base_name,
+                raw_entries,
+                offset_to_index,
+                resolved,
+                by_hash,
+                in_progress,
+                format,
+            )?;
+            let (base_type, base_content) = resolved[base_index].clone().unwrap();
+            (base_type, apply_delta(&base_content, delta)?)
+        }
+    };
+
+    let object_hash = {
+        let header = format!("{} {}\0", result.0.type_str(), result.1.len());
+        let mut buf = header.into_bytes();
+        buf.extend(&result.1);
+        hash(&buf, format)
+    };
+    by_hash.insert(object_hash, index);
+    resolved[index] = Some(result);
+    in_progress.remove(&index);
+    Ok(())
+}
+
+/// Finds the entry whose resolved hash matches `base_name`, resolving other
+/// not-yet-resolved entries (in index order, skipping the entry currently
+/// being resolved) until it turns up. Unlike an ofs-delta base — found
+/// directly by offset — a ref-delta's base is only known by hash, so it may
+/// sit anywhere in the pack, including after the entry that references it.
+#[allow(clippy::too_many_arguments)]
+fn resolve_ref_delta_base(
+    index: usize,
+    base_name: &str,
+    raw_entries: &[RawEntry],
+    offset_to_index: &HashMap<usize, usize>,
+    resolved: &mut Vec<Option<(EntryType, Vec<u8>)>>,
+    by_hash: &mut HashMap<String, usize>,
+    in_progress: &mut HashSet<usize>,
+    format: ObjectFormat,
+) -> Result<usize> {
+    if let Some(&found) = by_hash.get(base_name) {
+        return Ok(found);
+    }
+
+    for other in 0..raw_entries.len() {
+        if other == index || resolved[other].is_some() {
+            continue;
+        }
+        resolve_entry(
+            other,
+            raw_entries,
+            offset_to_index,
+            resolved,
+            by_hash,
+            in_progress,
+            format,
+        )?;
+        if let Some(&found) = by_hash.get(base_name) {
+            return Ok(found);
+        }
+    }
+
+    Err(anyhow!("ref-delta base {base_name} not found in pack"))
+}
+
+/// Parses a pack entry header: a type in bits 4-6 of the first byte and a
+/// size split across as many continuation bytes as needed (MSB = "more").
+/// Returns the type, the decompressed size and the number of header bytes.
+fn parse_entry_header(buf: &[u8]) -> Result<(EntryType, usize, usize)> {
+    let first = *buf.first().ok_or_else(|| anyhow!("Truncated entry header"))?;
+    let entry_type = EntryType::from_bits((first >> 4) & 0x7)?;
+
+    let mut size = (first & 0x0f) as usize;
+    let mut shift: u32 = 4;
+    let mut consumed = 1;
+    let mut more = first & 0x80 != 0;
+    while more {
+        let byte = *buf
+            .get(consumed)
+            .ok_or_else(|| anyhow!("Truncated entry header"))?;
+        let bits = ((byte & 0x7f) as usize)
+            .checked_shl(shift)
+            .ok_or_else(|| anyhow!("Pack entry header size overflows"))?;
+        size |= bits;
+        shift += 7;
+        consumed += 1;
+        more = byte & 0x80 != 0;
+    }
+    Ok((entry_type, size, consumed))
+}
+
+/// Parses the ofs-delta negative offset varint, which (unlike the size
+/// varints elsewhere in the format) accumulates most-significant-byte
+/// first and adds one for every continuation byte.
+fn parse_negative_offset(buf: &[u8]) -> Result<(usize, usize)> {
+    let mut byte = *buf.first().ok_or_else(|| anyhow!("Truncated ofs-delta offset"))?;
+    let mut consumed = 1;
+    let mut offset = (byte & 0x7f) as usize;
+    while byte & 0x80 != 0 {
+        byte = *buf
+            .get(consumed)
+            .ok_or_else(|| anyhow!("Truncated ofs-delta offset"))?;
+        consumed += 1;
+        offset += 1;
+        offset = offset
+            .checked_mul(1 << 7)
+            .ok_or_else(|| anyhow!("ofs-delta offset overflows"))?;
+        offset |= (byte & 0x7f) as usize;
+    }
+    Ok((offset, consumed))
+}
+
+/// Inflates a zlib stream of known decompressed `expected_size`, returning
+/// the decompressed bytes and the number of compressed bytes consumed so
+/// the caller can advance past this entry.
+fn inflate_at(buf: &[u8], expected_size: usize) -> Result<(Vec<u8>, usize)> {
+    let mut decoder = ZlibDecoder::new(buf);
+    let mut out = Vec::with_capacity(expected_size);
+    decoder
+        .read_to_end(&mut out)
+        .context("Failed to inflate pack entry")?;
+    if out.len() != expected_size {
+        return Err(anyhow!("Inflated entry size mismatch"));
+    }
+    let consumed = decoder.total_in() as usize;
+    Ok((out, consumed))
+}
+
+/// Reads a little-endian base-128 varint (MSB = "more bytes follow").
+fn read_size_varint(buf: &[u8], index: &mut usize) -> Result<usize> {
+    let mut size = 0usize;
+    let mut shift: u32 = 0;
+    loop {
+        let byte = *buf
+            .get(*index)
+            .ok_or_else(|| anyhow!("Truncated delta size"))?;
+        *index += 1;
+        let bits = ((byte & 0x7f) as usize)
+            .checked_shl(shift)
+            .ok_or_else(|| anyhow!("Delta size overflows"))?;
+        size |= bits;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    Ok(size)
+}
+
+/// Applies a git delta (as used by both ofs-delta and ref-delta entries) to
+/// `base`, producing the reconstructed target object bytes.
+fn apply_delta(base: &[u8], delta: &[u8]) -> Result<Vec<u8>> {
+    let mut i = 0;
+    let source_size = read_size_varint(delta, &mut i)?;
+    if source_size != base.len() {
+        return Err(anyhow!("Delta base size mismatch"));
+    }
+    let target_size = read_size_varint(delta, &mut i)?;
+    let mut out = Vec::with_capacity(target_size);
+
+    while i < delta.len() {
+        let op = delta[i];
+        i += 1;
+
+        if op & 0x80 != 0 {
+            let mut copy_offset: u32 = 0;
+            for bit in 0..4 {
+                if op & (1 << bit) != 0 {
+                    let byte = *delta
+                        .get(i)
+                        .ok_or_else(|| anyhow!("Truncated copy offset"))?;
+                    copy_offset |= (byte as u32) << (8 * bit);
+                    i += 1;
+                }
+            }
+            let mut copy_len: u32 = 0;
+            for bit in 0..3 {
+                if op & (1 << (4 + bit)) != 0 {
+                    let byte = *delta
+                        .get(i)
+                        .ok_or_else(|| anyhow!("Truncated copy length"))?;
+                    copy_len |= (byte as u32) << (8 * bit);
+                    i += 1;
+                }
+            }
+            let copy_len = if copy_len == 0 { 0x10000 } else { copy_len as usize };
+            let copy_offset = copy_offset as usize;
+            let end = copy_offset
+                .checked_add(copy_len)
+                .ok_or_else(|| anyhow!("Copy instruction overflows base"))?;
+            let chunk = base
+                .get(copy_offset..end)
+                .ok_or_else(|| anyhow!("Copy instruction out of bounds"))?;
+            out.extend_from_slice(chunk);
+        } else if op == 0 {
+            return Err(anyhow!("Invalid delta opcode 0"));
+        } else {
+            let length = op as usize;
+            let end = i + length;
+            let chunk = delta
+                .get(i..end)
+                .ok_or_else(|| anyhow!("Truncated insert instruction"))?;
+            out.extend_from_slice(chunk);
+            i = end;
+        }
+    }
+
+    if out.len() != target_size {
+        return Err(anyhow!("Delta target size mismatch"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn entry_header(type_bits: u8, mut size: usize) -> Vec<u8> {
+        let mut out = vec![];
+        let mut first = (type_bits << 4) | ((size & 0x0f) as u8);
+        size >>= 4;
+        if size > 0 {
+            first |= 0x80;
+        }
+        out.push(first);
+        while size > 0 {
+            let mut byte = (size & 0x7f) as u8;
+            size >>= 7;
+            if size > 0 {
+                byte |= 0x80;
+            }
+            out.push(byte);
+        }
+        out
+    }
+
+    fn encode_ofs_delta_offset(mut offset: u64) -> Vec<u8> {
+        let mut bytes = vec![(offset & 0x7f) as u8];
+        offset >>= 7;
+        while offset != 0 {
+            offset -= 1;
+            bytes.push((0x80 | (offset & 0x7f)) as u8);
+            offset >>= 7;
+        }
+        bytes.reverse();
+        bytes
+    }
+
+    fn zlib_compress(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn base_entry(type_bits: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = entry_header(type_bits, content.len());
+        out.extend(zlib_compress(content));
+        out
+    }
+
+    fn ofs_delta_entry(delta: &[u8], back_offset: u64) -> Vec<u8> {
+        let mut out = entry_header(6, delta.len());
+        out.extend(encode_ofs_delta_offset(back_offset));
+        out.extend(zlib_compress(delta));
+        out
+    }
+
+    fn ref_delta_entry(delta: &[u8], base_name: &[u8]) -> Vec<u8> {
+        let mut out = entry_header(7, delta.len());
+        out.extend(base_name);
+        out.extend(zlib_compress(delta));
+        out
+    }
+
+    fn build_pack(bodies: &[Vec<u8>], format: ObjectFormat) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(b"PACK");
+        data.extend_from_slice(&2u32.to_be_bytes());
+        data.extend_from_slice(&(bodies.len() as u32).to_be_bytes());
+        for body in bodies {
+            data.extend_from_slice(body);
+        }
+        let checksum = hex::decode(hash(&data, format)).unwrap();
+        data.extend(checksum);
+        data
+    }
+
+    #[test]
+    fn test_pack_file_multi_object() {
+        let blob = base_entry(3, b"what is up, doc?");
+        let tree_content = b"100644 file1.txt\0\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x11\x12\x13\x14";
+        let tree = base_entry(2, tree_content);
+        let pack = build_pack(&[blob, tree], ObjectFormat::Sha1);
+
+        let pack_file = PackFile::from_bytes(&pack, ObjectFormat::Sha1).unwrap();
+        assert_eq!(pack_file.objects.len(), 2);
+
+        let Object::Blob(blob) = &pack_file.objects[0] else {
+            panic!("Expected a blob");
+        };
+        assert_eq!(blob.content, b"what is up, doc?");
+
+        let Object::Tree(tree) = &pack_file.objects[1] else {
+            panic!("Expected a tree");
+        };
+        assert_eq!(tree.files.len(), 1);
+        assert_eq!(tree.files[0].name, "file1.txt");
+    }
+
+    #[test]
+    fn test_pack_file_ofs_delta() {
+        let base_content = b"hello world";
+        let base = base_entry(3, base_content);
+
+        // source_size=11, target_size=11, copy(offset=0, len=6) "hello ", insert "there"
+        let delta = [11u8, 11, 0x90, 0x06, 0x05, b't', b'h', b'e', b'r', b'e'];
+        // The base entry starts at pack offset 12 (right after the 12-byte
+        // pack header); the delta entry immediately follows it.
+        let back_offset = base.len() as u64;
+        let delta_entry = ofs_delta_entry(&delta, back_offset);
+
+        let pack = build_pack(&[base, delta_entry], ObjectFormat::Sha1);
+        let pack_file = PackFile::from_bytes(&pack, ObjectFormat::Sha1).unwrap();
+        assert_eq!(pack_file.objects.len(), 2);
+
+        let Object::Blob(base_obj) = &pack_file.objects[0] else {
+            panic!("Expected a blob");
+        };
+        assert_eq!(base_obj.content, b"hello world");
+
+        let Object::Blob(target) = &pack_file.objects[1] else {
+            panic!("Expected a blob");
+        };
+        assert_eq!(target.content, b"hello there");
+    }
+
+    #[test]
+    fn test_pack_file_ref_delta() {
+        let base_content = b"hello world";
+        let mut base_header_and_content = format!("blob {}\0", base_content.len()).into_bytes();
+        base_header_and_content.extend(base_content);
+        let base_name_hex = hash(&base_header_and_content, ObjectFormat::Sha1);
+        let base_name = hex::decode(base_name_hex).unwrap();
+
+        let base = base_entry(3, base_content);
+        let delta = [11u8, 11, 0x90, 0x06, 0x05, b't', b'h', b'e', b'r', b'e'];
+        let delta_entry = ref_delta_entry(&delta, &base_name);
+
+        let pack = build_pack(&[base, delta_entry], ObjectFormat::Sha1);
+        let pack_file = PackFile::from_bytes(&pack, ObjectFormat::Sha1).unwrap();
+        assert_eq!(pack_file.objects.len(), 2);
+
+        let Object::Blob(target) = &pack_file.objects[1] else {
+            panic!("Expected a blob");
+        };
+        assert_eq!(target.content, b"hello there");
+    }
+
+    #[test]
+    fn test_pack_file_truncated_input_errors() {
+        let blob = base_entry(3, b"what is up, doc?");
+        let mut pack = build_pack(&[blob], ObjectFormat::Sha1);
+        pack.truncate(pack.len() - 5);
+
+        let err = PackFile::from_bytes(&pack, ObjectFormat::Sha1);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_pack_file_self_referential_ofs_delta_errors() {
+        let delta = [11u8, 11, 0x90, 0x06, 0x05, b't', b'h', b'e', b'r', b'e'];
+        // back_offset = 0 makes this entry its own ofs-delta base.
+        let entry = ofs_delta_entry(&delta, 0);
+        let pack = build_pack(&[entry], ObjectFormat::Sha1);
+
+        let err = PackFile::from_bytes(&pack, ObjectFormat::Sha1);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_pack_file_ref_delta_before_base_resolves() {
+        let base_content = b"hello world";
+        let mut base_header_and_content = format!("blob {}\0", base_content.len()).into_bytes();
+        base_header_and_content.extend(base_content);
+        let base_name_hex = hash(&base_header_and_content, ObjectFormat::Sha1);
+        let base_name = hex::decode(base_name_hex).unwrap();
+
+        let delta = [11u8, 11, 0x90, 0x06, 0x05, b't', b'h', b'e', b'r', b'e'];
+        let delta_entry = ref_delta_entry(&delta, &base_name);
+        let base = base_entry(3, base_content);
+
+        // The ref-delta entry comes before the base it points to, which is
+        // valid PACK-format ordering (ref-delta bases aren't offset
+        // constrained the way ofs-delta bases are).
+        let pack = build_pack(&[delta_entry, base], ObjectFormat::Sha1);
+        let pack_file = PackFile::from_bytes(&pack, ObjectFormat::Sha1).unwrap();
+        assert_eq!(pack_file.objects.len(), 2);
+
+        let Object::Blob(target) = &pack_file.objects[0] else {
+            panic!("Expected a blob");
+        };
+        assert_eq!(target.content, b"hello there");
+    }
+
+    #[test]
+    fn test_pack_entry_header_varint_overflow_errors() {
+        // A type byte followed by far more continuation bytes than any
+        // real size varint needs, each with the continuation bit set, so
+        // the shift would run past the width of a `usize`.
+        let mut header = vec![(3 << 4) | 0x8f];
+        header.extend(std::iter::repeat_n(0xffu8, 12));
+        let pack = build_pack(&[header], ObjectFormat::Sha1);
+
+        let err = PackFile::from_bytes(&pack, ObjectFormat::Sha1);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_pack_file_sha256_format() {
+        let blob = base_entry(3, b"what is up, doc?");
+        let mut tree_content = b"100644 file1.txt\0".to_vec();
+        tree_content.extend(1u8..=32u8);
+        let tree = base_entry(2, &tree_content);
+
+        let pack = build_pack(&[blob, tree], ObjectFormat::Sha256);
+        let pack_file = PackFile::from_bytes(&pack, ObjectFormat::Sha256).unwrap();
+        assert_eq!(pack_file.objects.len(), 2);
+
+        let Object::Tree(tree) = &pack_file.objects[1] else {
+            panic!("Expected a tree");
+        };
+        assert_eq!(tree.files[0].name, "file1.txt");
+        assert_eq!(tree.files[0].hash, hex::encode((1u8..=32u8).collect::<Vec<u8>>()));
+    }
+
+    #[test]
+    fn test_pack_file_sha256_ref_delta() {
+        let base_content = b"hello world";
+        let mut base_header_and_content = format!("blob {}\0", base_content.len()).into_bytes();
+        base_header_and_content.extend(base_content);
+        let base_name_hex = hash(&base_header_and_content, ObjectFormat::Sha256);
+        let base_name = hex::decode(base_name_hex).unwrap();
+        assert_eq!(base_name.len(), 32);
+
+        let base = base_entry(3, base_content);
+        let delta = [11u8, 11, 0x90, 0x06, 0x05, b't', b'h', b'e', b'r', b'e'];
+        let delta_entry = ref_delta_entry(&delta, &base_name);
+
+        let pack = build_pack(&[base, delta_entry], ObjectFormat::Sha256);
+        let pack_file = PackFile::from_bytes(&pack, ObjectFormat::Sha256).unwrap();
+        assert_eq!(pack_file.objects.len(), 2);
+
+        let Object::Blob(target) = &pack_file.objects[1] else {
+            panic!("Expected a blob");
+        };
+        assert_eq!(target.content, b"hello there");
+    }
+}